@@ -1,6 +1,10 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
 use command_palette::CommandInterceptResult;
-use editor::{SortLinesCaseInsensitive, SortLinesCaseSensitive};
+use editor::{Editor, Point, SortLinesCaseInsensitive, SortLinesCaseSensitive};
 use gpui::{impl_actions, Action, AppContext};
+use once_cell::sync::Lazy;
 use serde_derive::Deserialize;
 use workspace::{SaveBehavior, Workspace};
 
@@ -20,7 +24,83 @@ pub struct GoToLine {
     pub line: u32,
 }
 
-impl_actions!(vim, [GoToLine]);
+/// Selects rows `start..=end` (zero-based) in the active editor, then runs
+/// `JoinLines` over that selection. Exists so `:10,20j` can reuse the same
+/// no-argument action that plain `:j` already dispatches.
+#[derive(Debug, Clone, PartialEq, Deserialize, Default)]
+pub struct JoinRange {
+    pub start: u32,
+    pub end: u32,
+}
+
+/// Selects rows `start..=end` (zero-based) in the active editor, then runs
+/// `DeleteLine` over that selection.
+#[derive(Debug, Clone, PartialEq, Deserialize, Default)]
+pub struct DeleteRange {
+    pub start: u32,
+    pub end: u32,
+}
+
+/// Selects rows `start..=end` (zero-based) in the active editor, then sorts
+/// that selection, case-sensitively unless `case_insensitive` is set.
+#[derive(Debug, Clone, PartialEq, Deserialize, Default)]
+pub struct SortRange {
+    pub start: u32,
+    pub end: u32,
+    pub case_insensitive: bool,
+}
+
+/// `:w {path}` / `:saveas {path}`: save the active buffer to an explicit
+/// path (resolved against the project root if relative) via the project's
+/// `fs`, rather than to the path it already has. `bang` forces the write
+/// through without prompting if `path` already has unsaved conflicting
+/// changes on disk.
+#[derive(Debug, Clone, PartialEq, Deserialize, Default)]
+pub struct SaveAs {
+    pub path: String,
+    pub bang: bool,
+}
+
+/// `:e {path}` / `:edit {path}`: open `path` into the workspace, relative to
+/// the project root if it isn't absolute.
+#[derive(Debug, Clone, PartialEq, Deserialize, Default)]
+pub struct EditPath {
+    pub path: String,
+}
+
+/// `:b {name}`: activate the open item whose display name matches `name`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Default)]
+pub struct JumpToBuffer {
+    pub name: String,
+}
+
+/// `:bd[elete]`: close the item whose display name matches `name`, or the
+/// active item if no name was given.
+#[derive(Debug, Clone, PartialEq, Deserialize, Default)]
+pub struct CloseBuffer {
+    pub name: Option<String>,
+}
+
+/// `:command`: list every registered ex-command, its aliases, and its doc
+/// string in a scratch buffer, so users can discover what's implemented
+/// without reading this file.
+#[derive(Debug, Clone, PartialEq, Deserialize, Default)]
+pub struct ListExCommands;
+
+impl_actions!(
+    vim,
+    [
+        GoToLine,
+        JoinRange,
+        DeleteRange,
+        SortRange,
+        SaveAs,
+        EditPath,
+        ListExCommands,
+        JumpToBuffer,
+        CloseBuffer
+    ]
+);
 
 pub fn init(cx: &mut AppContext) {
     cx.add_action(|_: &mut Workspace, action: &GoToLine, cx| {
@@ -29,234 +109,854 @@ pub fn init(cx: &mut AppContext) {
             move_cursor(vim, Motion::StartOfDocument, Some(action.line as usize), cx);
         });
     });
+
+    cx.add_action(|_: &mut Workspace, action: &JoinRange, cx| {
+        Vim::update(cx, |vim, cx| {
+            select_rows(vim, action.start, action.end, cx);
+            cx.dispatch_action(JoinLines.boxed_clone());
+        });
+    });
+
+    cx.add_action(|_: &mut Workspace, action: &DeleteRange, cx| {
+        Vim::update(cx, |vim, cx| {
+            select_rows(vim, action.start, action.end, cx);
+            cx.dispatch_action(editor::DeleteLine.boxed_clone());
+        });
+    });
+
+    cx.add_action(|_: &mut Workspace, action: &SortRange, cx| {
+        Vim::update(cx, |vim, cx| {
+            select_rows(vim, action.start, action.end, cx);
+            let sort = if action.case_insensitive {
+                SortLinesCaseInsensitive.boxed_clone()
+            } else {
+                SortLinesCaseSensitive.boxed_clone()
+            };
+            cx.dispatch_action(sort);
+        });
+    });
+
+    cx.add_action(|workspace: &mut Workspace, action: &SaveAs, cx| {
+        let Some(editor) = workspace
+            .active_item(cx)
+            .and_then(|item| item.downcast::<Editor>())
+        else {
+            return;
+        };
+        let Some(buffer) = editor.read(cx).buffer().read(cx).as_singleton() else {
+            return;
+        };
+        let path = resolve_edit_path(workspace, &action.path, cx);
+        let save_behavior = if action.bang {
+            SaveBehavior::SilentlyOverwrite
+        } else {
+            SaveBehavior::PromptOnConflict
+        };
+        let save = workspace.project().update(cx, |project, cx| {
+            project.save_buffer_as(buffer, path, save_behavior, cx)
+        });
+        save.detach_and_log_err(cx);
+    });
+
+    cx.add_action(|workspace: &mut Workspace, action: &EditPath, cx| {
+        let path = resolve_edit_path(workspace, &action.path, cx);
+        workspace.open_abs_path(path, false, cx).detach_and_log_err(cx);
+    });
+
+    cx.add_action(|workspace: &mut Workspace, action: &JumpToBuffer, cx| {
+        let Some(index) = find_item_by_name(workspace, &action.name, cx) else {
+            return;
+        };
+        workspace.activate_item(&workspace.items(cx).nth(index).unwrap(), cx);
+    });
+
+    cx.add_action(|workspace: &mut Workspace, action: &CloseBuffer, cx| {
+        let item = match &action.name {
+            Some(name) => find_item_by_name(workspace, name, cx)
+                .and_then(|index| workspace.items(cx).nth(index)),
+            None => workspace.active_item(cx),
+        };
+        let Some(item) = item else {
+            return;
+        };
+        workspace
+            .close_item_by_id(item.id(), SaveBehavior::PromptOnWrite, cx)
+            .detach_and_log_err(cx);
+    });
+
+    cx.add_action(|workspace: &mut Workspace, _: &ListExCommands, cx| {
+        let project = workspace.project().clone();
+        let buffer = project.update(cx, |project, cx| project.create_buffer(cx));
+        cx.spawn(|workspace, mut cx| async move {
+            let buffer = buffer.await?;
+            buffer.update(&mut cx, |buffer, cx| {
+                buffer.edit([(0..0, command_listing())], None, cx)
+            })?;
+            workspace.update(&mut cx, |workspace, cx| {
+                let editor = cx.add_view(|cx| Editor::for_buffer(buffer, Some(project), cx));
+                workspace.add_item(Box::new(editor), cx);
+            })
+        })
+        .detach_and_log_err(cx);
+    });
 }
 
-pub fn command_interceptor(mut query: &str, _: &AppContext) -> Option<CommandInterceptResult> {
-    // Note: this is a very poor simulation of vim's command palette.
-    // In the future we should adjust it to handle parsing range syntax,
-    // and then calling the appropriate commands with/without ranges.
-    //
-    // We also need to support passing arguments to commands like :w
-    // (ideally with filename autocompletion).
-    //
-    // For now, you can only do a replace on the % range, and you can
-    // only use a specific line number range to "go to line"
-    while query.starts_with(":") {
-        query = &query[1..];
+/// Renders every registered ex-command, its aliases, and its doc string as
+/// plain text, for the `:command` scratch buffer.
+fn command_listing() -> String {
+    let mut commands: Vec<&ExCommand> = REGISTRY.iter().collect();
+    commands.sort_by_key(|command| command.name);
+
+    let mut text = String::from("# Ex-commands\n\n");
+    for command in commands {
+        text.push_str(&format!(":{:<12} {}\n", command.name, command.doc));
+        if !command.aliases.is_empty() {
+            text.push_str(&format!("             aliases: {}\n", command.aliases.join(", ")));
+        }
     }
+    text
+}
 
-    let (name, action) = match query {
-        // save and quit
-        "w" | "wr" | "wri" | "writ" | "write" => (
-            "write",
-            workspace::Save {
-                save_behavior: Some(SaveBehavior::PromptOnConflict),
-            }
-            .boxed_clone(),
-        ),
-        "w!" | "wr!" | "wri!" | "writ!" | "write!" => (
-            "write!",
-            workspace::Save {
-                save_behavior: Some(SaveBehavior::SilentlyOverwrite),
-            }
-            .boxed_clone(),
-        ),
-        "q" | "qu" | "qui" | "quit" => (
-            "quit",
-            workspace::CloseActiveItem {
-                save_behavior: Some(SaveBehavior::PromptOnWrite),
-            }
-            .boxed_clone(),
-        ),
-        "q!" | "qu!" | "qui!" | "quit!" => (
-            "quit!",
-            workspace::CloseActiveItem {
-                save_behavior: Some(SaveBehavior::DontSave),
-            }
-            .boxed_clone(),
-        ),
-        "wq" => (
-            "wq",
-            workspace::CloseActiveItem {
-                save_behavior: Some(SaveBehavior::PromptOnConflict),
-            }
-            .boxed_clone(),
-        ),
-        "wq!" => (
-            "wq!",
-            workspace::CloseActiveItem {
-                save_behavior: Some(SaveBehavior::SilentlyOverwrite),
-            }
-            .boxed_clone(),
-        ),
-        "x" | "xi" | "xit" | "exi" | "exit" => (
-            "exit",
-            workspace::CloseActiveItem {
-                save_behavior: Some(SaveBehavior::PromptOnConflict),
-            }
-            .boxed_clone(),
-        ),
-        "x!" | "xi!" | "xit!" | "exi!" | "exit!" => (
-            "exit!",
-            workspace::CloseActiveItem {
-                save_behavior: Some(SaveBehavior::SilentlyOverwrite),
-            }
-            .boxed_clone(),
-        ),
-        "wa" | "wal" | "wall" => (
-            "wall",
-            workspace::SaveAll {
-                save_behavior: Some(SaveBehavior::PromptOnConflict),
-            }
-            .boxed_clone(),
-        ),
-        "wa!" | "wal!" | "wall!" => (
-            "wall!",
-            workspace::SaveAll {
-                save_behavior: Some(SaveBehavior::SilentlyOverwrite),
-            }
-            .boxed_clone(),
-        ),
-        "qa" | "qal" | "qall" | "quita" | "quital" | "quitall" => (
-            "quitall",
-            workspace::CloseAllItemsAndPanes {
-                save_behavior: Some(SaveBehavior::PromptOnWrite),
-            }
-            .boxed_clone(),
-        ),
-        "qa!" | "qal!" | "qall!" | "quita!" | "quital!" | "quitall!" => (
-            "quitall!",
-            workspace::CloseAllItemsAndPanes {
-                save_behavior: Some(SaveBehavior::DontSave),
-            }
-            .boxed_clone(),
-        ),
-        "xa" | "xal" | "xall" => (
-            "xall",
-            workspace::CloseAllItemsAndPanes {
-                save_behavior: Some(SaveBehavior::PromptOnConflict),
+/// Resolves an `:edit`/`:saveas` path argument against the project's first
+/// worktree root when it isn't already absolute, so `:e src/main.rs` opens
+/// relative to the project rather than being handed straight to an
+/// absolute-path API.
+fn resolve_edit_path(workspace: &Workspace, path: &str, cx: &AppContext) -> PathBuf {
+    let path = PathBuf::from(path);
+    if path.is_absolute() {
+        return path;
+    }
+    workspace
+        .project()
+        .read(cx)
+        .worktrees(cx)
+        .next()
+        .map(|worktree| worktree.read(cx).abs_path().join(&path))
+        .unwrap_or(path)
+}
+
+/// Finds the index (per `workspace.items(cx)`) of the first open item whose
+/// display name contains `name`, mirroring Helix's buffer completer.
+fn find_item_by_name(workspace: &Workspace, name: &str, cx: &AppContext) -> Option<usize> {
+    workspace
+        .items(cx)
+        .position(|item| item.tab_description(0, cx).is_some_and(|d| d.contains(name)))
+}
+
+/// Expands the active editor's selection to cover rows `start..=end`
+/// (zero-based), so that a no-argument operator action (join, delete, sort)
+/// runs over exactly the range an ex-command specified.
+fn select_rows(vim: &mut Vim, start: u32, end: u32, cx: &mut gpui::ViewContext<Vim>) {
+    let Some(editor) = vim.active_editor.clone().and_then(|e| e.upgrade(cx)) else {
+        return;
+    };
+    editor.update(cx, |editor, cx| {
+        editor.change_selections(None, cx, |selections| {
+            selections.select_ranges([Point::new(start, 0)..Point::new(end, 0)]);
+        });
+    });
+}
+
+/// The arguments that follow the command name, e.g. in `:sort i` this is `i`.
+/// Split out so handlers don't each have to re-derive it from the raw query.
+#[derive(Debug, Clone, Default)]
+pub struct ExArgs<'a> {
+    /// Whether the command was typed with a trailing `!`, e.g. `:q!`.
+    pub bang: bool,
+    /// Everything after the command name and its bang, whitespace-trimmed.
+    pub raw: &'a str,
+    /// `raw`, whitespace-split with `'`/`"` quoting, e.g. `"foo 'a b'"` is
+    /// `["foo", "a b"]`.
+    pub args: Vec<&'a str>,
+    /// The resolved, zero-based, start..=end row span of a leading
+    /// `:10,20` style range, if one was given and the command accepts it.
+    pub range: Option<(u32, u32)>,
+}
+
+/// What a command's first argument completes against in the command
+/// palette, mirroring Helix's `Completer`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum Completer {
+    #[default]
+    None,
+    Filename,
+    Buffer,
+    Directory,
+}
+
+/// A single ex-command, registered once and resolved by name or alias instead
+/// of being hand-matched against every typed prefix. Modeled on Helix's
+/// `TypableCommand`.
+struct ExCommand {
+    /// The canonical, full spelling of the command, e.g. `"write"`.
+    name: &'static str,
+    /// Other full spellings and short abbreviations that should resolve to
+    /// this command, e.g. `["w", "wr", "wri", "writ"]`.
+    aliases: &'static [&'static str],
+    /// Shown in the command palette alongside the resolved command.
+    doc: &'static str,
+    /// Whether a leading `:10,20` style range is meaningful for this
+    /// command. If one is given for a command where this is `false`, the
+    /// whole ex-command is rejected rather than silently ignoring the range.
+    accepts_range: bool,
+    /// What the first argument completes against, if anything.
+    completer: Completer,
+    handler: fn(ExArgs) -> Option<Box<dyn Action>>,
+}
+
+macro_rules! ex_commands {
+    ($($name:literal [$($alias:literal),* $(,)?] ($doc:literal)
+        $(range: $accepts_range:literal)? $(complete: $completer:ident)? => $handler:expr),* $(,)?) => {
+        &[$(
+            ExCommand {
+                name: $name,
+                aliases: &[$($alias),*],
+                doc: $doc,
+                accepts_range: ex_commands!(@range $($accepts_range)?),
+                completer: ex_commands!(@completer $($completer)?),
+                handler: $handler,
             }
-            .boxed_clone(),
-        ),
-        "xa!" | "xal!" | "xall!" => (
-            "xall!",
-            workspace::CloseAllItemsAndPanes {
-                save_behavior: Some(SaveBehavior::SilentlyOverwrite),
+        ),*]
+    };
+    (@range) => { false };
+    (@range $accepts_range:literal) => { $accepts_range };
+    (@completer) => { Completer::None };
+    (@completer $completer:ident) => { Completer::$completer };
+}
+
+static REGISTRY: &[ExCommand] = ex_commands![
+    "write" ["w", "wr", "wri", "writ"] ("Save the current buffer, optionally to {path}") complete: Filename => |args| Some(
+        match args.args.first() {
+            Some(path) => SaveAs {
+                path: path.to_string(),
+                bang: args.bang,
             }
             .boxed_clone(),
-        ),
-        "wqa" | "wqal" | "wqall" => (
-            "wqall",
-            workspace::CloseAllItemsAndPanes {
-                save_behavior: Some(SaveBehavior::PromptOnConflict),
+            None => workspace::Save {
+                save_behavior: Some(if args.bang {
+                    SaveBehavior::SilentlyOverwrite
+                } else {
+                    SaveBehavior::PromptOnConflict
+                }),
             }
             .boxed_clone(),
-        ),
-        "wqa!" | "wqal!" | "wqall!" => (
-            "wqall!",
-            workspace::CloseAllItemsAndPanes {
-                save_behavior: Some(SaveBehavior::SilentlyOverwrite),
+        }
+    ),
+    "saveas" ["sav", "sava"] ("Save the current buffer to {path}") complete: Filename => |args| {
+        let path = args.args.first()?;
+        Some(
+            SaveAs {
+                path: path.to_string(),
+                bang: args.bang,
             }
             .boxed_clone(),
-        ),
-        "cq" | "cqu" | "cqui" | "cquit" | "cq!" | "cqu!" | "cqui!" | "cquit!" => {
-            ("cquit!", zed_actions::Quit.boxed_clone())
+        )
+    },
+    "edit" ["e", "ed", "edi"] ("Open {path} into the workspace") complete: Filename => |args| {
+        let path = args.args.first()?;
+        Some(EditPath { path: path.to_string() }.boxed_clone())
+    },
+    "quit" ["q", "qu", "qui"] ("Close the active item") => |args| Some(
+        workspace::CloseActiveItem {
+            save_behavior: Some(if args.bang {
+                SaveBehavior::DontSave
+            } else {
+                SaveBehavior::PromptOnWrite
+            }),
+        }
+        .boxed_clone()
+    ),
+    "wq" [] ("Save the current buffer, then close it") => |args| Some(
+        workspace::CloseActiveItem {
+            save_behavior: Some(if args.bang {
+                SaveBehavior::SilentlyOverwrite
+            } else {
+                SaveBehavior::PromptOnConflict
+            }),
+        }
+        .boxed_clone()
+    ),
+    "exit" ["x", "xi", "xit", "exi"] ("Save the current buffer, then close it") => |args| Some(
+        workspace::CloseActiveItem {
+            save_behavior: Some(if args.bang {
+                SaveBehavior::SilentlyOverwrite
+            } else {
+                SaveBehavior::PromptOnConflict
+            }),
+        }
+        .boxed_clone()
+    ),
+    "wall" ["wa", "wal"] ("Save all open buffers") => |args| Some(
+        workspace::SaveAll {
+            save_behavior: Some(if args.bang {
+                SaveBehavior::SilentlyOverwrite
+            } else {
+                SaveBehavior::PromptOnConflict
+            }),
+        }
+        .boxed_clone()
+    ),
+    "quitall" ["qa", "qal", "quita", "quital"] ("Close every item and pane") => |args| Some(
+        workspace::CloseAllItemsAndPanes {
+            save_behavior: Some(if args.bang {
+                SaveBehavior::DontSave
+            } else {
+                SaveBehavior::PromptOnWrite
+            }),
+        }
+        .boxed_clone()
+    ),
+    "xall" ["xa", "xal"] ("Save all open buffers, then close every item and pane") => |args| Some(
+        workspace::CloseAllItemsAndPanes {
+            save_behavior: Some(if args.bang {
+                SaveBehavior::SilentlyOverwrite
+            } else {
+                SaveBehavior::PromptOnConflict
+            }),
         }
+        .boxed_clone()
+    ),
+    "wqall" ["wqa", "wqal"] ("Save all open buffers, then close every item and pane") => |args| Some(
+        workspace::CloseAllItemsAndPanes {
+            save_behavior: Some(if args.bang {
+                SaveBehavior::SilentlyOverwrite
+            } else {
+                SaveBehavior::PromptOnConflict
+            }),
+        }
+        .boxed_clone()
+    ),
+    "cquit" ["cq", "cqu", "cqui"] ("Quit Zed without saving") => |_| Some(zed_actions::Quit.boxed_clone()),
 
-        // pane management
-        "sp" | "spl" | "spli" | "split" => ("split", workspace::SplitUp.boxed_clone()),
-        "vs" | "vsp" | "vspl" | "vspli" | "vsplit" => {
-            ("vsplit", workspace::SplitLeft.boxed_clone())
+    "split" ["sp", "spl", "spli"] ("Split the pane above") => |_| Some(workspace::SplitUp.boxed_clone()),
+    "vsplit" ["vs", "vsp", "vspl", "vspli"] ("Split the pane to the left") => |_| Some(workspace::SplitLeft.boxed_clone()),
+    "new" [] ("Open a new file in a split above") => |_| Some(
+        workspace::NewFileInDirection(workspace::SplitDirection::Up).boxed_clone()
+    ),
+    "vnew" ["vne"] ("Open a new file in a split to the left") => |_| Some(
+        workspace::NewFileInDirection(workspace::SplitDirection::Left).boxed_clone()
+    ),
+    "tabedit" ["tabe", "tabed", "tabedi"] ("Open a new file in a new tab") => |_| Some(workspace::NewFile.boxed_clone()),
+    "tabnew" [] ("Open a new file in a new tab") => |_| Some(workspace::NewFile.boxed_clone()),
+    "bnext" ["bn", "bne", "bnex"] ("Activate the next buffer") => |_| Some(workspace::ActivateNextItem.boxed_clone()),
+    "bprevious" ["bp", "bpr", "bpre", "bprev", "bprevi", "bprevio", "bpreviou"] ("Activate the previous buffer") => |_| Some(workspace::ActivatePrevItem.boxed_clone()),
+    "buffer" ["b", "bu", "buf", "buff", "buffe"] ("Activate the open buffer matching {name}") complete: Buffer => |args| {
+        let name = args.args.first()?;
+        Some(JumpToBuffer { name: name.to_string() }.boxed_clone())
+    },
+    "bdelete" ["bd", "bde", "bdel", "bdele", "bdelet"] ("Close the open buffer matching {name}, or the active one") complete: Buffer => |args| Some(
+        CloseBuffer {
+            name: args.args.first().map(|name| name.to_string()),
         }
-        "new" => (
-            "new",
-            workspace::NewFileInDirection(workspace::SplitDirection::Up).boxed_clone(),
-        ),
-        "vne" | "vnew" => (
-            "vnew",
-            workspace::NewFileInDirection(workspace::SplitDirection::Left).boxed_clone(),
-        ),
-        "tabe" | "tabed" | "tabedi" | "tabedit" => ("tabedit", workspace::NewFile.boxed_clone()),
-        "tabnew" => ("tabnew", workspace::NewFile.boxed_clone()),
+        .boxed_clone()
+    ),
+    "tabnext" ["tabn", "tabne", "tabnex"] ("Activate the next tab") => |_| Some(workspace::ActivateNextItem.boxed_clone()),
+    "tabprevious" [
+        "tabp", "tabpr", "tabpre", "tabprev", "tabprevi", "tabprevio", "tabpreviou"
+    ] ("Activate the previous tab") => |_| Some(workspace::ActivatePrevItem.boxed_clone()),
+    "tabNext" ["tabN", "tabNe", "tabNex"] ("Activate the previous tab") => |_| Some(workspace::ActivatePrevItem.boxed_clone()),
+    "tabclose" ["tabc", "tabcl", "tabclo", "tabclos"] ("Close the active tab") => |args| Some(
+        workspace::CloseActiveItem {
+            save_behavior: Some(if args.bang {
+                SaveBehavior::DontSave
+            } else {
+                SaveBehavior::PromptOnWrite
+            }),
+        }
+        .boxed_clone()
+    ),
+
+    "clist" ["cl", "cli", "clis"] ("List diagnostics") => |_| Some(diagnostics::Deploy.boxed_clone()),
+    "cc" [] ("Show the diagnostic under the cursor") => |_| Some(editor::Hover.boxed_clone()),
+    "ll" [] ("Show the diagnostic under the cursor") => |_| Some(editor::Hover.boxed_clone()),
+    "cnext" ["cn", "cne", "cnex"] ("Go to the next diagnostic") => |_| Some(editor::GoToDiagnostic.boxed_clone()),
+    "lnext" ["lne", "lnex"] ("Go to the next diagnostic") => |_| Some(editor::GoToDiagnostic.boxed_clone()),
+    "cprevious" [
+        "cpr", "cpre", "cprev", "cprevi", "cprevio", "cpreviou"
+    ] ("Go to the previous diagnostic") => |_| Some(editor::GoToPrevDiagnostic.boxed_clone()),
+    "cNext" ["cN", "cNe", "cNex"] ("Go to the previous diagnostic") => |_| Some(editor::GoToPrevDiagnostic.boxed_clone()),
+    "lprevious" [
+        "lp", "lpr", "lpre", "lprev", "lprevi", "lprevio", "lpreviou"
+    ] ("Go to the previous diagnostic") => |_| Some(editor::GoToPrevDiagnostic.boxed_clone()),
+    "lNext" ["lN", "lNe", "lNex"] ("Go to the previous diagnostic") => |_| Some(editor::GoToPrevDiagnostic.boxed_clone()),
 
-        "tabn" | "tabne" | "tabnex" | "tabnext" => {
-            ("tabnext", workspace::ActivateNextItem.boxed_clone())
+    "join" ["j", "jo", "joi"] ("Join the current line with the next") range: true => |args| Some(
+        match args.range {
+            Some((start, end)) => JoinRange { start, end }.boxed_clone(),
+            None => JoinLines.boxed_clone(),
         }
-        "tabp" | "tabpr" | "tabpre" | "tabprev" | "tabprevi" | "tabprevio" | "tabpreviou"
-        | "tabprevious" => ("tabprevious", workspace::ActivatePrevItem.boxed_clone()),
-        "tabN" | "tabNe" | "tabNex" | "tabNext" => {
-            ("tabNext", workspace::ActivatePrevItem.boxed_clone())
+    ),
+    "delete" [
+        "d", "de", "del", "dele", "delet", "dl", "dell", "delel", "deletl", "deletel",
+        "dp", "dep", "delp", "delep", "deletp", "deletep"
+    ] ("Delete the current line") range: true => |args| Some(
+        match args.range {
+            Some((start, end)) => DeleteRange { start, end }.boxed_clone(),
+            None => editor::DeleteLine.boxed_clone(),
         }
-        "tabc" | "tabcl" | "tabclo" | "tabclos" | "tabclose" => (
-            "tabclose",
-            workspace::CloseActiveItem {
-                save_behavior: Some(SaveBehavior::PromptOnWrite),
-            }
-            .boxed_clone(),
-        ),
+    ),
+    "sort" ["sor"] ("Sort the buffer, or `i` for case-insensitive") range: true => |args| {
+        let case_insensitive = match args.raw {
+            "" => false,
+            "i" => true,
+            _ => return None,
+        };
+        Some(match args.range {
+            Some((start, end)) => SortRange { start, end, case_insensitive }.boxed_clone(),
+            None if case_insensitive => SortLinesCaseInsensitive.boxed_clone(),
+            None => SortLinesCaseSensitive.boxed_clone(),
+        })
+    },
+
+    "$" [] ("Go to the end of the document") => |_| Some(EndOfDocument.boxed_clone()),
 
-        // quickfix / loclist (merged together for now)
-        "cl" | "cli" | "clis" | "clist" => ("clist", diagnostics::Deploy.boxed_clone()),
-        "cc" => ("cc", editor::Hover.boxed_clone()),
-        "ll" => ("ll", editor::Hover.boxed_clone()),
-        "cn" | "cne" | "cnex" | "cnext" => ("cnext", editor::GoToDiagnostic.boxed_clone()),
-        "lne" | "lnex" | "lnext" => ("cnext", editor::GoToDiagnostic.boxed_clone()),
+    "command" [] ("List every ex-command Zed implements") => |_| Some(ListExCommands.boxed_clone()),
+];
 
-        "cpr" | "cpre" | "cprev" | "cprevi" | "cprevio" | "cpreviou" | "cprevious" => {
-            ("cprevious", editor::GoToPrevDiagnostic.boxed_clone())
+static COMMANDS: Lazy<HashMap<&'static str, &'static ExCommand>> = Lazy::new(|| {
+    let mut commands = HashMap::new();
+    for command in REGISTRY {
+        commands.insert(command.name, command);
+        for alias in command.aliases {
+            commands.insert(*alias, command);
         }
-        "cN" | "cNe" | "cNex" | "cNext" => ("cNext", editor::GoToPrevDiagnostic.boxed_clone()),
-        "lp" | "lpr" | "lpre" | "lprev" | "lprevi" | "lprevio" | "lpreviou" | "lprevious" => {
-            ("lprevious", editor::GoToPrevDiagnostic.boxed_clone())
+    }
+    commands
+});
+
+/// One endpoint of a `:range` (the bit before or after the comma), before
+/// it's been resolved against the current buffer.
+#[derive(Debug, Clone)]
+enum Address {
+    /// An absolute, 1-based line number, as typed (`:42`).
+    Line(u32),
+    /// `.`, the current line.
+    Current,
+    /// `$`, the last line.
+    Last,
+    /// `'x`, a named mark.
+    Mark(char),
+    /// `/pat/` or `?pat?`, the next/previous line matching `pat`.
+    Search { pattern: String, backwards: bool },
+}
+
+/// An [`Address`] plus a trailing `+N`/`-N` offset, e.g. the `$-1` in `:$-1,$d`.
+#[derive(Debug, Clone)]
+struct Anchor {
+    address: Address,
+    offset: i32,
+}
+
+/// A parsed (but not yet resolved) `:start,end` range prefix.
+#[derive(Debug, Clone)]
+struct Range {
+    start: Anchor,
+    end: Anchor,
+}
+
+fn parse_offset(mut rest: &str) -> (i32, &str) {
+    let mut offset = 0i32;
+    loop {
+        let sign = match rest.as_bytes().first() {
+            Some(b'+') => 1,
+            Some(b'-') => -1,
+            _ => break,
+        };
+        let digits = &rest[1..];
+        let end = digits
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(digits.len());
+        let amount: i32 = if end == 0 {
+            1
+        } else {
+            digits[..end].parse().unwrap_or(1)
+        };
+        offset += sign * amount;
+        rest = &digits[end..];
+    }
+    (offset, rest)
+}
+
+/// Parses a single range endpoint (an [`Address`] plus optional offset) from
+/// the front of `input`, returning the anchor and the unconsumed remainder.
+fn parse_anchor(input: &str) -> Option<(Anchor, &str)> {
+    let (address, rest) = if let Some(rest) = input.strip_prefix('.') {
+        (Address::Current, rest)
+    } else if let Some(rest) = input.strip_prefix('$') {
+        (Address::Last, rest)
+    } else if let Some(rest) = input.strip_prefix('\'') {
+        let mark = rest.chars().next()?;
+        (Address::Mark(mark), &rest[mark.len_utf8()..])
+    } else if let Some(rest) = input.strip_prefix('/') {
+        let end = rest.find('/').unwrap_or(rest.len());
+        let rest_after = rest[end..].strip_prefix('/').unwrap_or(&rest[end..]);
+        (
+            Address::Search {
+                pattern: rest[..end].to_string(),
+                backwards: false,
+            },
+            rest_after,
+        )
+    } else if let Some(rest) = input.strip_prefix('?') {
+        let end = rest.find('?').unwrap_or(rest.len());
+        let rest_after = rest[end..].strip_prefix('?').unwrap_or(&rest[end..]);
+        (
+            Address::Search {
+                pattern: rest[..end].to_string(),
+                backwards: true,
+            },
+            rest_after,
+        )
+    } else if matches!(input.as_bytes().first(), Some(b'+') | Some(b'-')) {
+        // An address can be a bare offset with no address in front of it,
+        // e.g. the `+2` in `:+2d` - that's shorthand for `.+2`.
+        (Address::Current, input)
+    } else {
+        let end = input
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(input.len());
+        if end == 0 {
+            return None;
         }
-        "lN" | "lNe" | "lNex" | "lNext" => ("lNext", editor::GoToPrevDiagnostic.boxed_clone()),
+        (Address::Line(input[..end].parse().ok()?), &input[end..])
+    };
+
+    let (offset, rest) = parse_offset(rest);
+    Some((Anchor { address, offset }, rest))
+}
+
+/// Parses a leading `:range` off of `query` (`%`, `N,M`, `.,$`, `'a,'b`, a
+/// bare address, etc.), returning it along with whatever follows. Returns
+/// `None` for the range half when `query` doesn't start with a range, or
+/// when it's just a bare address with nothing after it (e.g. `:5`, which is
+/// "go to line" rather than a ranged command).
+fn parse_range(query: &str) -> (Option<Range>, &str) {
+    if let Some(rest) = query.strip_prefix('%') {
+        return (
+            Some(Range {
+                start: Anchor {
+                    address: Address::Line(1),
+                    offset: 0,
+                },
+                end: Anchor {
+                    address: Address::Last,
+                    offset: 0,
+                },
+            }),
+            rest,
+        );
+    }
+
+    let Some((start, rest)) = parse_anchor(query) else {
+        return (None, query);
+    };
+
+    let (range, rest) = match rest.strip_prefix(',') {
+        Some(rest) => match parse_anchor(rest) {
+            Some((end, rest)) => (Range { start, end }, rest),
+            None => return (None, query),
+        },
+        None => (
+            Range {
+                start: start.clone(),
+                end: start,
+            },
+            rest,
+        ),
+    };
+
+    if rest.is_empty() {
+        // Nothing follows the address, so this wasn't a range prefixing a
+        // command - let the existing bare-number/search handling deal with it.
+        return (None, query);
+    }
+
+    (Some(range), rest)
+}
 
-        // modify the buffer (should accept [range])
-        "j" | "jo" | "joi" | "join" => ("join", JoinLines.boxed_clone()),
-        "d" | "de" | "del" | "dele" | "delet" | "delete" | "dl" | "dell" | "delel" | "deletl"
-        | "deletel" | "dp" | "dep" | "delp" | "delep" | "deletp" | "deletep" => {
-            ("delete", editor::DeleteLine.boxed_clone())
+/// Resolves a single [`Anchor`] to a zero-based row, clamped to the buffer's
+/// line count. `reference_row` is what `.` and a relative search measure
+/// from - the original cursor row, for both addresses of a range, matching
+/// how vim resolves both halves of `addr1,addr2` relative to the cursor.
+fn resolve_anchor(anchor: &Anchor, vim: &Vim, cx: &AppContext, reference_row: u32) -> Option<u32> {
+    let editor = vim.active_editor.clone()?.upgrade(cx)?;
+    let snapshot = editor.read(cx).buffer().read(cx).snapshot(cx);
+    let last_row = snapshot.max_point().row;
+
+    let base_row = match &anchor.address {
+        Address::Current => reference_row,
+        Address::Last => last_row,
+        Address::Line(line) => line.saturating_sub(1),
+        Address::Mark(mark) => vim.marks.get(&mark.to_string())?.head().row,
+        Address::Search { pattern, backwards } => {
+            let matches = snapshot.matches(pattern, reference_row, *backwards)?;
+            matches.row
         }
-        "sor" | "sor " | "sort" | "sort " => ("sort", SortLinesCaseSensitive.boxed_clone()),
-        "sor i" | "sort i" => ("sort i", SortLinesCaseInsensitive.boxed_clone()),
-
-        // goto (other ranges handled under _ => )
-        "$" => ("$", EndOfDocument.boxed_clone()),
-
-        _ => {
-            if query.starts_with("/") || query.starts_with("?") {
-                (
-                    query,
-                    FindCommand {
-                        query: query[1..].to_string(),
-                        backwards: query.starts_with("?"),
-                    }
-                    .boxed_clone(),
-                )
-            } else if query.starts_with("%") {
-                (
-                    query,
-                    ReplaceCommand {
-                        query: query.to_string(),
-                    }
-                    .boxed_clone(),
-                )
-            } else if let Ok(line) = query.parse::<u32>() {
-                (query, GoToLine { line }.boxed_clone())
-            } else {
-                return None;
-            }
+    };
+
+    Some((base_row as i64 + anchor.offset as i64).clamp(0, last_row as i64) as u32)
+}
+
+/// Resolves a parsed [`Range`] to a zero-based, inclusive `(start, end)` row
+/// span, swapping the endpoints if they were given in descending order.
+fn resolve_range(range: &Range, cx: &AppContext) -> Option<(u32, u32)> {
+    let vim = Vim::read(cx)?;
+    let editor = vim.active_editor.clone()?.upgrade(cx)?;
+    let cursor_row = editor.read(cx).selections.newest::<Point>(cx).head().row;
+
+    let mut start = resolve_anchor(&range.start, vim, cx, cursor_row)?;
+    let mut end = resolve_anchor(&range.end, vim, cx, cursor_row)?;
+    if start > end {
+        std::mem::swap(&mut start, &mut end);
+    }
+    Some((start, end))
+}
+
+/// Splits `:wq!  ` into `("wq", true, "")`: the command name, whether a bang
+/// was present, and the (trimmed) remainder passed to the handler as args.
+fn split_command(query: &str) -> (&str, bool, &str) {
+    let (head, rest) = match query.find(char::is_whitespace) {
+        Some(index) => (&query[..index], query[index..].trim()),
+        None => (query, ""),
+    };
+    match head.strip_suffix('!') {
+        Some(name) => (name, true, rest),
+        None => (head, false, rest),
+    }
+}
+
+/// Resolves a command name against the registry, matching either an exact
+/// name/alias or an unambiguous prefix of one, e.g. `"writ"` resolves to
+/// `"write"` but `"c"` is ambiguous between `"cc"`, `"cnext"`, etc. A prefix
+/// that matches several aliases of the *same* command (e.g. `"vn"` matching
+/// both `"vne"` and `"vnew"`) is still unambiguous - only distinct commands
+/// count towards ambiguity.
+fn resolve_command(name: &str) -> Option<&'static ExCommand> {
+    if name.is_empty() {
+        return None;
+    }
+    if let Some(command) = COMMANDS.get(name) {
+        return Some(*command);
+    }
+
+    let mut matches = COMMANDS
+        .iter()
+        .filter(|(key, _)| key.starts_with(name))
+        .map(|(_, command)| *command);
+    let first = matches.next()?;
+    if matches.any(|command| !std::ptr::eq(command, first)) {
+        return None;
+    }
+    Some(first)
+}
+
+pub fn command_interceptor(mut query: &str, cx: &AppContext) -> Option<CommandInterceptResult> {
+    while query.starts_with(":") {
+        query = &query[1..];
+    }
+    let full_query = query;
+
+    let (range, rest) = parse_range(query);
+
+    // A range with nothing after it, and no command name, isn't a range at
+    // all - it's a bare `/pattern`, `?pattern`, or `42` "go to" query.
+    if range.is_none() {
+        if query.starts_with("/") || query.starts_with("?") {
+            return Some(build_result(
+                full_query,
+                query,
+                FindCommand {
+                    query: query[1..].to_string(),
+                    backwards: query.starts_with("?"),
+                }
+                .boxed_clone(),
+                None,
+            ));
+        }
+        if let Ok(line) = query.parse::<u32>() {
+            return Some(build_result(
+                full_query,
+                query,
+                GoToLine { line }.boxed_clone(),
+                None,
+            ));
         }
+    }
+
+    if is_substitute(rest) {
+        // `%s/.../...` already works end-to-end, so leave it as-is; for any
+        // other range form (marks, search, `.`/`$`, offsets) rewrite it to
+        // the equivalent absolute line numbers, which is the one range
+        // syntax the substitute parser is guaranteed to understand.
+        let query = if query.starts_with('%') {
+            full_query.to_string()
+        } else {
+            match range.and_then(|range| resolve_range(&range, cx)) {
+                Some((start, end)) => format!("{},{}{}", start + 1, end + 1, rest),
+                None => full_query.to_string(),
+            }
+        };
+        return Some(build_result(
+            full_query,
+            full_query,
+            ReplaceCommand { query }.boxed_clone(),
+            Some(SUBSTITUTE_DOC),
+        ));
+    }
+
+    let (name, bang, raw) = split_command(rest);
+    let command = resolve_command(name)?;
+
+    let range = match range {
+        Some(range) if command.accepts_range => Some(resolve_range(&range, cx)?),
+        Some(_) => return None,
+        None => None,
     };
 
-    let string = ":".to_owned() + name;
-    let positions = generate_positions(&string, query);
+    let args = ExArgs {
+        bang,
+        raw,
+        args: split_args(raw),
+        range,
+    };
+    let action = (command.handler)(args)?;
+    Some(build_result(
+        full_query,
+        command.name,
+        action,
+        Some(command.doc),
+    ))
+}
+
+/// `:s`/`:%s` isn't in [`REGISTRY`] (it has its own delimiter-based syntax
+/// rather than a bare name), so it needs its own doc string for the palette.
+const SUBSTITUTE_DOC: &str = "Replace a pattern with a replacement, optionally over a range";
+
+/// Splits `raw` on whitespace, treating `'...'`/`"..."` as a single
+/// argument even if it contains spaces, e.g. `foo 'a b'` -> `["foo", "a b"]`.
+fn split_args(raw: &str) -> Vec<&str> {
+    let mut args = Vec::new();
+    let mut rest = raw;
+
+    loop {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            break;
+        }
+
+        let quote = rest.starts_with('"').then_some('"').or(rest
+            .starts_with('\'')
+            .then_some('\''));
+
+        let (arg, remainder) = if let Some(quote) = quote {
+            let body = &rest[1..];
+            match body.find(quote) {
+                Some(end) => (&body[..end], &body[end + 1..]),
+                None => (body, ""),
+            }
+        } else {
+            match rest.find(char::is_whitespace) {
+                Some(end) => (&rest[..end], &rest[end..]),
+                None => (rest, ""),
+            }
+        };
+
+        args.push(arg);
+        rest = remainder;
+    }
+
+    args
+}
+
+/// `is_substitute` and `build_result` factor out the two bits that every
+/// branch of [`command_interceptor`] needs: recognizing `:s/.../...` (which,
+/// unlike other commands, has its own delimiter-based syntax rather than a
+/// bare name) and stamping out the `(display string, highlight positions,
+/// doc)` the palette needs from whatever name ended up being resolved.
+fn is_substitute(rest: &str) -> bool {
+    rest.strip_prefix('s')
+        .and_then(|rest| rest.chars().next())
+        .is_some_and(|c| !c.is_alphanumeric() && c != '_')
+}
 
-    Some(CommandInterceptResult {
+fn build_result(
+    full_query: &str,
+    name: &str,
+    action: Box<dyn Action>,
+    doc: Option<&'static str>,
+) -> CommandInterceptResult {
+    let string = ":".to_owned() + name;
+    let positions = generate_positions(&string, full_query);
+    CommandInterceptResult {
         action,
         string,
         positions,
-    })
+        doc,
+    }
+}
+
+/// Completions for the argument currently being typed after an ex-command
+/// name, e.g. `:w src/m` -> paths under the project worktrees starting with
+/// `src/m`. Returns an empty list for commands with no completer, or while
+/// the command name itself is still being typed.
+pub fn completions_for(query: &str, workspace: &Workspace, cx: &AppContext) -> Vec<String> {
+    let mut query = query;
+    while query.starts_with(':') {
+        query = &query[1..];
+    }
+    let (_, rest) = parse_range(query);
+    if !rest.contains(char::is_whitespace) {
+        return Vec::new();
+    }
+    let (name, _, raw) = split_command(rest);
+    let Some(command) = resolve_command(name) else {
+        return Vec::new();
+    };
+    let partial = split_args(raw).last().copied().unwrap_or("");
+
+    match command.completer {
+        Completer::Filename | Completer::Directory => {
+            complete_paths(partial, workspace, cx, command.completer == Completer::Directory)
+        }
+        Completer::Buffer => complete_buffers(partial, workspace, cx),
+        Completer::None => Vec::new(),
+    }
+}
+
+/// Completions for `:b`/`:bdelete`'s buffer-name argument: the display name
+/// of every open workspace item matching `partial`, mirroring Helix's
+/// buffer completer.
+fn complete_buffers(partial: &str, workspace: &Workspace, cx: &AppContext) -> Vec<String> {
+    workspace
+        .items(cx)
+        .filter_map(|item| item.tab_description(0, cx))
+        .map(|name| name.to_string())
+        .filter(|name| name.contains(partial))
+        .collect()
+}
+
+fn complete_paths(
+    partial: &str,
+    workspace: &Workspace,
+    cx: &AppContext,
+    directories_only: bool,
+) -> Vec<String> {
+    let project = workspace.project().read(cx);
+    let mut matches: Vec<String> = project
+        .worktrees(cx)
+        .flat_map(|worktree| {
+            let worktree = worktree.read(cx);
+            worktree
+                .entries(false)
+                .filter(|entry| !directories_only || entry.is_dir())
+                .map(|entry| entry.path.to_string_lossy().into_owned())
+                .collect::<Vec<_>>()
+        })
+        .filter(|path| path.starts_with(partial))
+        .collect();
+    matches.sort();
+    matches.truncate(50);
+    matches
 }
 
 fn generate_positions(string: &str, query: &str) -> Vec<usize> {
@@ -286,6 +986,7 @@ mod test {
     use std::path::Path;
 
     use crate::test::{NeovimBackedTestContext, VimTestContext};
+    use editor::Editor;
     use gpui::{executor::Foreground, TestAppContext};
     use indoc::indoc;
 
@@ -313,6 +1014,158 @@ mod test {
         .await;
     }
 
+    #[gpui::test]
+    async fn test_command_range_delete(cx: &mut TestAppContext) {
+        let mut cx = NeovimBackedTestContext::new(cx).await;
+
+        cx.set_shared_state(indoc! {"
+            ˇa
+            b
+            c
+            d"})
+            .await;
+        cx.simulate_shared_keystrokes([":", "2", ",", "3", "d", "enter"])
+            .await;
+        cx.assert_shared_state(indoc! {"
+            a
+            ˇd"})
+            .await;
+    }
+
+    #[gpui::test]
+    async fn test_command_range_offset(cx: &mut TestAppContext) {
+        let mut cx = NeovimBackedTestContext::new(cx).await;
+
+        cx.set_shared_state(indoc! {"
+            ˇa
+            b
+            c"})
+            .await;
+        cx.simulate_shared_keystrokes([":", "+", "1", "d", "enter"])
+            .await;
+        cx.assert_shared_state(indoc! {"
+            a
+            ˇc"})
+            .await;
+    }
+
+    #[gpui::test]
+    async fn test_command_range_substitute(cx: &mut TestAppContext) {
+        let mut cx = NeovimBackedTestContext::new(cx).await;
+
+        cx.set_shared_state(indoc! {"
+            ˇa
+            b
+            b
+            b"})
+            .await;
+        cx.simulate_shared_keystrokes([
+            ":", "2", ",", "3", "s", "/", "b", "/", "d", "enter",
+        ])
+        .await;
+        cx.assert_shared_state(indoc! {"
+            a
+            d
+            ˇd
+            b"})
+            .await;
+    }
+
+    #[gpui::test]
+    async fn test_command_range_mark(cx: &mut TestAppContext) {
+        let mut cx = NeovimBackedTestContext::new(cx).await;
+
+        cx.set_shared_state(indoc! {"
+            ˇa
+            b
+            c
+            d"})
+            .await;
+        cx.simulate_shared_keystrokes(["m", "a"]).await;
+        cx.simulate_shared_keystrokes(["2", "j"]).await;
+        cx.simulate_shared_keystrokes(["m", "b"]).await;
+        cx.simulate_shared_keystrokes([
+            ":", "'", "a", ",", "'", "b", "d", "enter",
+        ])
+        .await;
+        cx.assert_shared_state(indoc! {"
+            ˇd"})
+            .await;
+    }
+
+    #[gpui::test]
+    async fn test_command_range_search(cx: &mut TestAppContext) {
+        let mut cx = NeovimBackedTestContext::new(cx).await;
+
+        cx.set_shared_state(indoc! {"
+            ˇa
+            x
+            b
+            x
+            c"})
+            .await;
+        cx.simulate_shared_keystrokes([
+            ":", "/", "x", "/", ",", "/", "x", "/", "d", "enter",
+        ])
+        .await;
+        cx.assert_shared_state(indoc! {"
+            a
+            ˇb
+            x
+            c"})
+            .await;
+    }
+
+    #[gpui::test]
+    async fn test_command_range_to_end(cx: &mut TestAppContext) {
+        let mut cx = NeovimBackedTestContext::new(cx).await;
+
+        cx.set_shared_state(indoc! {"
+            a
+            ˇb
+            c
+            d"})
+            .await;
+        cx.simulate_shared_keystrokes([":", ".", ",", "$", "d", "enter"])
+            .await;
+        cx.assert_shared_state(indoc! {"
+            ˇa"})
+            .await;
+    }
+
+    #[gpui::test]
+    async fn test_command_range_percent(cx: &mut TestAppContext) {
+        let mut cx = NeovimBackedTestContext::new(cx).await;
+
+        cx.set_shared_state(indoc! {"
+            ˇa
+            b
+            c"})
+            .await;
+        cx.simulate_shared_keystrokes([":", "%", "d", "enter"]).await;
+        cx.assert_shared_state("ˇ").await;
+    }
+
+    #[gpui::test]
+    async fn test_command_range_join(cx: &mut TestAppContext) {
+        let mut cx = NeovimBackedTestContext::new(cx).await;
+
+        cx.set_shared_state(indoc! {"
+            ˇa
+            b
+            c
+            d"})
+            .await;
+        cx.simulate_shared_keystrokes([":", "1", ",", "3", "j", "enter"])
+            .await;
+        // hack: our cursor positioning after a join command is wrong
+        cx.simulate_shared_keystrokes(["^"]).await;
+        cx.assert_shared_state(indoc! {"
+            ˇa b c
+            d"})
+            .await;
+    }
+
     #[gpui::test]
     async fn test_command_goto(cx: &mut TestAppContext) {
         let mut cx = NeovimBackedTestContext::new(cx).await;
@@ -415,6 +1268,63 @@ mod test {
         assert_eq!(fs.load(&path).await.unwrap(), "@@\n");
     }
 
+    #[gpui::test]
+    async fn test_command_write_as(cx: &mut TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        let fs = cx.workspace(|workspace, cx| workspace.project().read(cx).fs().clone());
+
+        cx.simulate_keystrokes(["i", "@", "escape"]);
+        cx.simulate_keystrokes([":", "s", "a", "v", "e", "a", "s", " "]);
+        cx.simulate_keystrokes(["/", "r", "o", "o", "t", "/", "d", "i", "r", "/", "a", ".", "r", "s"]);
+        cx.simulate_keystrokes(["enter"]);
+
+        assert_eq!(
+            fs.load(Path::new("/root/dir/a.rs")).await.unwrap(),
+            "@\n"
+        );
+
+        // A relative path resolves against the worktree root, like `:e` does.
+        cx.simulate_keystrokes([":", "s", "a", "v", "e", "a", "s", " ", "b", ".", "r", "s"]);
+        cx.simulate_keystrokes(["enter"]);
+        assert_eq!(
+            fs.load(Path::new("/root/dir/b.rs")).await.unwrap(),
+            "@\n"
+        );
+
+        // `!` force-overwrites a conflicting file instead of prompting.
+        fs.as_fake()
+            .write_file_internal(Path::new("/root/dir/b.rs"), "oops\n".to_string())
+            .unwrap();
+        cx.simulate_keystrokes([":", "w", "!", " ", "b", ".", "r", "s"]);
+        cx.simulate_keystrokes(["enter"]);
+        let window = cx.window;
+        assert!(!window.has_pending_prompt(cx.cx));
+        assert_eq!(fs.load(Path::new("/root/dir/b.rs")).await.unwrap(), "@\n");
+    }
+
+    #[gpui::test]
+    async fn test_command_completions(cx: &mut TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        let fs = cx.workspace(|workspace, cx| workspace.project().read(cx).fs().clone());
+        fs.as_fake()
+            .insert_tree(
+                "/root/dir",
+                serde_json::json!({
+                    "one.rs": "",
+                    "two.rs": "",
+                }),
+            )
+            .await;
+
+        let completions =
+            cx.workspace(|workspace, cx| super::completions_for("w one", workspace, cx));
+        assert_eq!(completions, vec!["one.rs".to_string()]);
+
+        let completions =
+            cx.workspace(|workspace, cx| super::completions_for("w nonexistent", workspace, cx));
+        assert!(completions.is_empty());
+    }
+
     #[gpui::test]
     async fn test_command_quit(cx: &mut TestAppContext) {
         let mut cx = VimTestContext::new(cx, true).await;
@@ -424,4 +1334,54 @@ mod test {
         cx.simulate_keystrokes([":", "q", "enter"]);
         cx.workspace(|workspace, cx| assert_eq!(workspace.items(cx).count(), 1));
     }
+
+    #[gpui::test]
+    async fn test_command_buffer_navigation(cx: &mut TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        let first = cx.workspace(|workspace, cx| workspace.active_item(cx).unwrap().id());
+        cx.simulate_keystrokes([":", "n", "e", "w", "enter"]);
+        let second = cx.workspace(|workspace, cx| workspace.active_item(cx).unwrap().id());
+        assert_ne!(first, second);
+
+        cx.simulate_keystrokes([":", "b", "p", "enter"]);
+        cx.workspace(|workspace, cx| assert_eq!(workspace.active_item(cx).unwrap().id(), first));
+
+        cx.simulate_keystrokes([":", "b", "n", "enter"]);
+        cx.workspace(|workspace, cx| assert_eq!(workspace.active_item(cx).unwrap().id(), second));
+
+        cx.simulate_keystrokes([":", "b", "d", "enter"]);
+        cx.workspace(|workspace, cx| assert_eq!(workspace.items(cx).count(), 1));
+    }
+
+    #[gpui::test]
+    async fn test_command_doc(cx: &mut TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+        cx.workspace(|_, cx| {
+            let result = super::command_interceptor("wqa", cx);
+            assert_eq!(
+                result.unwrap().doc,
+                Some("Save all open buffers, then close every item and pane")
+            );
+        });
+    }
+
+    #[gpui::test]
+    async fn test_command_list_ex_commands(cx: &mut TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        cx.simulate_keystrokes([":", "c", "o", "m", "m", "a", "n", "d", "enter"]);
+        cx.cx.foreground().run_until_parked();
+
+        cx.workspace(|workspace, cx| {
+            let editor = workspace
+                .active_item(cx)
+                .unwrap()
+                .downcast::<Editor>()
+                .unwrap();
+            let text = editor.read(cx).text(cx);
+            assert!(text.contains(":write"));
+            assert!(text.contains("aliases: w, wr, wri, writ"));
+        });
+    }
 }